@@ -2,10 +2,16 @@
 
 use alcoholic_jwt::{token_kid, validate, ValidJWT, Validation, JWKS};
 use async_trait::async_trait;
+use chrono::{Duration, Utc};
 use serde::Deserialize;
 
 use crate::error::{Auth0Result, Error};
-use crate::Auth0Client;
+use crate::jwks::JwksCache;
+use crate::{Auth0Client, GrantType};
+
+/// The margin of safety applied to the cached access token's expiry so a
+/// token about to expire isn't handed out only to die mid-flight.
+const ACCESS_TOKEN_EXPIRY_SKEW_SECONDS: i64 = 30;
 
 /// Trait for authenticating an Auth0 client.
 #[async_trait]
@@ -27,6 +33,22 @@ pub trait Authenticatable {
     async fn authenticate(&mut self) -> Auth0Result<()>;
     /// Returns the access token if autenticated or `None` if it is not.
     fn access_token(&self) -> Option<String>;
+    /// Returns a still-valid access token, re-authenticating first if the
+    /// client has none yet or the cached one has expired.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn new_client() -> auth0_client::error::Auth0Result<()> {
+    /// # use auth0_client::authorization::Authenticatable;
+    /// let mut client =
+    ///     auth0_client::Auth0Client::new("client_id", "client_secret", "domain", "audience");
+    ///
+    /// let token = client.valid_access_token().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn valid_access_token(&mut self) -> Auth0Result<String>;
 }
 
 /// The token type we use to authenticate.
@@ -40,50 +62,246 @@ enum TokenType {
 #[serde(rename_all = "snake_case")]
 struct AccessTokenResponse {
     pub access_token: String,
+    pub expires_in: i64,
+    #[allow(dead_code)]
+    pub token_type: TokenType,
+    pub refresh_token: Option<String>,
 }
 
-#[async_trait]
-impl Authenticatable for Auth0Client {
-    async fn authenticate(&mut self) -> Auth0Result<()> {
-        let url = format!("{}/oauth/token", self.domain).replace("//", "/");
+/// The standard OAuth2 error body returned by the token endpoint on failure.
+#[derive(Deserialize)]
+struct TokenEndpointErrorResponse {
+    error: String,
+    error_description: Option<String>,
+}
 
-        log::debug!("Starting authentication at {url}...");
+impl Auth0Client {
+    /// Adds this client's credentials to a token request body: either its
+    /// plaintext `client_secret`, or, when configured for `private_key_jwt`,
+    /// a freshly signed `client_assertion`.
+    fn apply_client_authentication(
+        &self,
+        body: &mut std::collections::HashMap<&str, String>,
+    ) -> Auth0Result<()> {
+        match &self.private_key {
+            Some(private_key) => {
+                body.insert("client_assertion", self.sign_client_assertion(private_key)?);
+                body.insert(
+                    "client_assertion_type",
+                    "urn:ietf:params:oauth:client-assertion-type:jwt-bearer".to_owned(),
+                );
+            }
+            None => {
+                body.insert("client_secret", self.client_secret.clone());
+            }
+        }
 
-        let body = {
-            let mut body = std::collections::HashMap::new();
+        Ok(())
+    }
 
-            body.insert("grant_type", self.grant_type.to_string());
-            body.insert("client_id", self.client_id.clone());
-            body.insert("client_secret", self.client_secret.clone());
-            body.insert("audience", self.audience.clone());
-            body
+    /// Builds and RS256-signs a `private_key_jwt` client assertion, per
+    /// https://www.rfc-editor.org/rfc/rfc7523.
+    fn sign_client_assertion(&self, private_key: &[u8]) -> Auth0Result<String> {
+        use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+
+        #[derive(serde::Serialize)]
+        struct ClientAssertionClaims {
+            iss: String,
+            sub: String,
+            aud: String,
+            iat: i64,
+            exp: i64,
+            jti: String,
+        }
+
+        let now = Utc::now();
+        let claims = ClientAssertionClaims {
+            iss: self.client_id.clone(),
+            sub: self.client_id.clone(),
+            aud: format!("{}/oauth/token", self.domain).replace("//", "/"),
+            iat: now.timestamp(),
+            exp: (now + Duration::seconds(60)).timestamp(),
+            jti: uuid::Uuid::new_v4().to_string(),
+        };
+
+        let key = if private_key.starts_with(b"-----BEGIN") {
+            EncodingKey::from_rsa_pem(private_key).map_err(Error::ClientAssertionSigning)?
+        } else {
+            EncodingKey::from_rsa_der(private_key)
         };
 
+        encode(&Header::new(Algorithm::RS256), &claims, &key)
+            .map_err(Error::ClientAssertionSigning)
+    }
+
+    /// Posts `body` to the `/oauth/token` endpoint and stores the resulting
+    /// access (and, if present, refresh) token on success.
+    ///
+    /// Shared by every grant (`client_credentials`, `authorization_code`,
+    /// `refresh_token`, ...): only the request body differs between them.
+    async fn exchange_token(
+        &mut self,
+        body: std::collections::HashMap<&str, String>,
+    ) -> Auth0Result<()> {
+        let url = format!("{}/oauth/token", self.domain).replace("//", "/");
+
+        log::debug!("Starting authentication at {url}...");
+
         let response = self.http_client.post(&url).json(&body).send().await?;
         let status = response.status();
         let resp_body = response.text().await?;
 
         log::debug!("Response from Auth0 ({}): {resp_body}", status.as_u16());
 
+        if !status.is_success() {
+            let token_error = serde_json::from_str::<TokenEndpointErrorResponse>(&resp_body)
+                .unwrap_or(TokenEndpointErrorResponse {
+                    error: "unknown_error".to_owned(),
+                    error_description: Some(resp_body),
+                });
+
+            return Err(Error::TokenEndpoint {
+                error: token_error.error,
+                error_description: token_error.error_description,
+                status,
+            });
+        }
+
         let response = serde_json::from_str::<AccessTokenResponse>(&resp_body)?;
 
         self.access_token = Some(response.access_token);
+        self.token_expires_at = Some(Utc::now() + Duration::seconds(response.expires_in));
+        if response.refresh_token.is_some() {
+            self.refresh_token = response.refresh_token;
+        }
         Ok(())
     }
 
+    /// Exchanges an authorization code (optionally with a PKCE
+    /// `code_verifier`) for an access token, for interactive/user-facing
+    /// flows rather than machine-to-machine `client_credentials`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn new_client() -> auth0_client::error::Auth0Result<()> {
+    /// let mut client =
+    ///     auth0_client::Auth0Client::new("client_id", "client_secret", "domain", "audience");
+    ///
+    /// client
+    ///     .authenticate_with_code("code", "https://example.com/callback", None)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn authenticate_with_code(
+        &mut self,
+        code: &str,
+        redirect_uri: &str,
+        code_verifier: Option<&str>,
+    ) -> Auth0Result<()> {
+        let mut body = std::collections::HashMap::new();
+
+        body.insert("grant_type", GrantType::AuthorizationCode.to_string());
+        body.insert("client_id", self.client_id.clone());
+        body.insert("code", code.to_owned());
+        body.insert("redirect_uri", redirect_uri.to_owned());
+        if let Some(code_verifier) = code_verifier {
+            body.insert("code_verifier", code_verifier.to_owned());
+        }
+        self.apply_client_authentication(&mut body)?;
+
+        self.exchange_token(body).await
+    }
+
+    /// Redeems the client's stored refresh token for a new access token.
+    ///
+    /// Returns [`Error::MissingRefreshToken`] if the client never obtained
+    /// one (e.g. it only ever used `client_credentials`, which Auth0 doesn't
+    /// issue refresh tokens for).
+    pub async fn refresh(&mut self) -> Auth0Result<()> {
+        let refresh_token = self
+            .refresh_token
+            .clone()
+            .ok_or(Error::MissingRefreshToken)?;
+
+        let mut body = std::collections::HashMap::new();
+
+        body.insert("grant_type", GrantType::RefreshToken.to_string());
+        body.insert("client_id", self.client_id.clone());
+        body.insert("refresh_token", refresh_token);
+        self.apply_client_authentication(&mut body)?;
+
+        self.exchange_token(body).await
+    }
+}
+
+#[async_trait]
+impl Authenticatable for Auth0Client {
+    async fn authenticate(&mut self) -> Auth0Result<()> {
+        let body = {
+            let mut body = std::collections::HashMap::new();
+
+            body.insert("grant_type", self.grant_type.to_string());
+            body.insert("client_id", self.client_id.clone());
+            body.insert("audience", self.audience.clone());
+            self.apply_client_authentication(&mut body)?;
+            body
+        };
+
+        self.exchange_token(body).await
+    }
+
     fn access_token(&self) -> Option<String> {
         self.access_token.clone()
     }
+
+    async fn valid_access_token(&mut self) -> Auth0Result<String> {
+        if !self.access_token_valid() {
+            if self.refresh_token.is_some() {
+                self.refresh().await?;
+            } else {
+                self.authenticate().await?;
+            }
+        }
+
+        Ok(self
+            .access_token
+            .clone()
+            .expect("authenticate() always sets an access token on success"))
+    }
+}
+
+impl Auth0Client {
+    /// Returns `true` if the client holds an access token that hasn't expired
+    /// yet, within a small skew margin.
+    pub fn access_token_valid(&self) -> bool {
+        match (&self.access_token, self.token_expires_at) {
+            (Some(_), Some(expires_at)) => {
+                Utc::now() + Duration::seconds(ACCESS_TOKEN_EXPIRY_SKEW_SECONDS) < expires_at
+            }
+            _ => false,
+        }
+    }
 }
 
 /// Fetches the JWKS from the given URI.
-async fn fetch_jwks(uri: &str) -> Auth0Result<JWKS> {
+pub(crate) async fn fetch_jwks(uri: &str) -> Auth0Result<JWKS> {
     let res = reqwest::get(uri).await?;
     let val = res.json::<JWKS>().await?;
 
     Ok(val)
 }
 
+/// Decodes a JWT's `kid` header claim.
+///
+/// Returns `Error::JwtMissingKid` both when the token fails to decode and
+/// when it decodes but simply carries no `kid` (e.g. any attacker-supplied
+/// token with a bare header) — neither case should ever panic.
+fn decode_kid(token: &str) -> Auth0Result<String> {
+    token_kid(token).ok().flatten().ok_or(Error::JwtMissingKid)
+}
+
 /// Validates a JWT token and returns its decoded payload.
 ///
 /// # Arguments
@@ -110,16 +328,145 @@ pub async fn valid_jwt(
     validations: Vec<Validation>,
 ) -> Auth0Result<ValidJWT> {
     let jwks = fetch_jwks(&format!("{authority}/.well-known/jwks.json")).await?;
-    let kid = match token_kid(token) {
-        Ok(res) => res.expect("failed to decode kid"),
-        Err(_) => return Err(Error::JwtMissingKid),
-    };
+    let kid = decode_kid(token)?;
     let jwk = jwks.find(&kid).ok_or(Error::JwtMissingKid)?;
     let res = validate(token, jwk, validations)?;
 
     Ok(res)
 }
 
+/// Validates a JWT token like [`valid_jwt`], but sources the JWKS from a
+/// shared [`JwksCache`] instead of refetching it on every call.
+///
+/// If the token's `kid` isn't found in the cached set, the cache is forced to
+/// refresh once before giving up, so a rotated signing key doesn't require
+/// waiting out the cache's TTL.
+///
+/// # Example
+/// ```
+/// # async fn validate_jwt() -> auth0_client::error::Auth0Result<()> {
+/// # use std::time::Duration;
+/// # use alcoholic_jwt::Validation;
+/// # use auth0_client::authorization::valid_jwt_cached;
+/// # use auth0_client::jwks::JwksCache;
+/// let cache = JwksCache::new(Duration::from_secs(300));
+///
+/// valid_jwt_cached(
+///     &cache,
+///     "...jwt_token...",
+///     "authority_to_retreive_jwks_from",
+///     vec![Validation::SubjectPresent, Validation::NotExpired],
+/// ).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn valid_jwt_cached(
+    cache: &JwksCache,
+    token: &str,
+    authority: &str,
+    validations: Vec<Validation>,
+) -> Auth0Result<ValidJWT> {
+    let kid = decode_kid(token)?;
+
+    let jwks = cache.get(authority).await?;
+    if let Some(jwk) = jwks.find(&kid) {
+        return Ok(validate(token, jwk, validations)?);
+    }
+
+    let jwks = cache.refresh(authority).await?;
+    let jwk = jwks.find(&kid).ok_or(Error::JwtMissingKid)?;
+
+    Ok(validate(token, jwk, validations)?)
+}
+
+/// Validates a JWT like [`valid_jwt`] and deserializes its validated claims
+/// into `T`, instead of leaving callers to hand-parse the raw claims
+/// [`serde_json::Value`].
+///
+/// # Example
+/// ```
+/// # async fn validate_jwt() -> auth0_client::error::Auth0Result<()> {
+/// # use alcoholic_jwt::Validation;
+/// # use auth0_client::authorization::valid_jwt_claims;
+/// # use serde::Deserialize;
+/// #[derive(Deserialize)]
+/// struct Claims {
+///     sub: String,
+/// }
+///
+/// let claims: Claims = valid_jwt_claims(
+///     "...jwt_token...",
+///     "authority_to_retreive_jwks_from",
+///     vec![Validation::SubjectPresent, Validation::NotExpired],
+/// ).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn valid_jwt_claims<T>(
+    token: &str,
+    authority: &str,
+    validations: Vec<Validation>,
+) -> Auth0Result<T>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let valid_jwt = valid_jwt(token, authority, validations).await?;
+
+    serde_json::from_value(valid_jwt.claims).map_err(Error::ClaimsDeserialization)
+}
+
+/// Validates a JWT like [`valid_jwt_claims`], but sources the JWKS from a
+/// shared [`JwksCache`] like [`valid_jwt_cached`] instead of refetching it on
+/// every call.
+///
+/// # Example
+/// ```
+/// # async fn validate_jwt() -> auth0_client::error::Auth0Result<()> {
+/// # use std::time::Duration;
+/// # use alcoholic_jwt::Validation;
+/// # use auth0_client::authorization::valid_jwt_claims_cached;
+/// # use auth0_client::jwks::JwksCache;
+/// # use serde::Deserialize;
+/// #[derive(Deserialize)]
+/// struct Claims {
+///     sub: String,
+/// }
+///
+/// let cache = JwksCache::new(Duration::from_secs(300));
+/// let claims: Claims = valid_jwt_claims_cached(
+///     &cache,
+///     "...jwt_token...",
+///     "authority_to_retreive_jwks_from",
+///     vec![Validation::SubjectPresent, Validation::NotExpired],
+/// ).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn valid_jwt_claims_cached<T>(
+    cache: &JwksCache,
+    token: &str,
+    authority: &str,
+    validations: Vec<Validation>,
+) -> Auth0Result<T>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let valid_jwt = valid_jwt_cached(cache, token, authority, validations).await?;
+
+    serde_json::from_value(valid_jwt.claims).map_err(Error::ClaimsDeserialization)
+}
+
+/// Splits a JWT's OAuth2 `scope` claim (a space-separated string) into
+/// individual scopes, for authorization checks. Returns an empty `Vec` if the
+/// claim is absent or isn't a string.
+pub fn scopes_from_claims(claims: &serde_json::Value) -> Vec<String> {
+    claims
+        .get("scope")
+        .and_then(|scope| scope.as_str())
+        .map(|scope| scope.split_whitespace().map(str::to_owned).collect())
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use mockito::{mock, Mock};
@@ -140,11 +487,40 @@ mod tests {
         mock("POST", "/oauth/token")
             .with_status(200)
             .with_body(
-                json!({ "access_token": "access_token", "token_type": "Bearer" }).to_string(),
+                json!({
+                    "access_token": "access_token",
+                    "token_type": "Bearer",
+                    "expires_in": 86400,
+                })
+                .to_string(),
             )
             .create()
     }
 
+    mod decode_kid {
+        use super::*;
+
+        #[test]
+        fn errors_instead_of_panicking_on_an_undecodable_token() {
+            match decode_kid("not-a-jwt") {
+                Err(Error::JwtMissingKid) => (),
+                other => panic!("Expected Error::JwtMissingKid but got {other:?}"),
+            }
+        }
+
+        #[test]
+        fn errors_instead_of_panicking_on_a_header_without_a_kid() {
+            // header `{"alg":"RS256","typ":"JWT"}`, payload `{"sub":"user"}`, no `kid`.
+            let token = "eyJhbGciOiAiUlMyNTYiLCAidHlwIjogIkpXVCJ9.\
+                         eyJzdWIiOiAidXNlciJ9.sig";
+
+            match decode_kid(token) {
+                Err(Error::JwtMissingKid) => (),
+                other => panic!("Expected Error::JwtMissingKid but got {other:?}"),
+            }
+        }
+    }
+
     mod authenticate {
         use super::*;
 
@@ -156,6 +532,249 @@ mod tests {
             client.authenticate().await.unwrap();
             assert_eq!(client.access_token, Some("access_token".to_owned()));
         }
+
+        #[tokio::test]
+        async fn save_the_token_expiry_to_the_client() {
+            let _m = auth_mock();
+            let mut client = new_client();
+
+            client.authenticate().await.unwrap();
+            assert!(client.token_expires_at.unwrap() > Utc::now());
+        }
+
+        #[tokio::test]
+        async fn errored_with_token_endpoint_error() {
+            let _m = mock("POST", "/oauth/token")
+                .with_status(401)
+                .with_body(
+                    json!({
+                        "error": "unauthorized_client",
+                        "error_description": "Unauthorized",
+                    })
+                    .to_string(),
+                )
+                .create();
+            let mut client = new_client();
+
+            match client.authenticate().await {
+                Err(Error::TokenEndpoint {
+                    error,
+                    error_description,
+                    status,
+                }) => {
+                    assert_eq!(error, "unauthorized_client");
+                    assert_eq!(error_description.as_deref(), Some("Unauthorized"));
+                    assert_eq!(status, reqwest::StatusCode::UNAUTHORIZED);
+                }
+                other => panic!("Expected Error::TokenEndpoint but got {other:?}"),
+            }
+        }
+    }
+
+    mod authenticate_with_code {
+        use super::*;
+
+        fn code_mock() -> Mock {
+            mock("POST", "/oauth/token")
+                .with_status(200)
+                .with_body(
+                    json!({
+                        "access_token": "access_token",
+                        "token_type": "Bearer",
+                        "expires_in": 86400,
+                        "refresh_token": "refresh_token",
+                    })
+                    .to_string(),
+                )
+                .create()
+        }
+
+        #[tokio::test]
+        async fn saves_the_access_and_refresh_tokens() {
+            let _m = code_mock();
+            let mut client = new_client();
+
+            client
+                .authenticate_with_code("code", "https://example.com/callback", None)
+                .await
+                .unwrap();
+
+            assert_eq!(client.access_token, Some("access_token".to_owned()));
+            assert_eq!(client.refresh_token, Some("refresh_token".to_owned()));
+        }
+
+        #[tokio::test]
+        async fn works_with_a_pkce_code_verifier() {
+            let _m = code_mock();
+            let mut client = new_client();
+
+            client
+                .authenticate_with_code(
+                    "code",
+                    "https://example.com/callback",
+                    Some("code_verifier"),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(client.access_token, Some("access_token".to_owned()));
+        }
+    }
+
+    mod refresh {
+        use super::*;
+
+        #[tokio::test]
+        async fn errors_without_a_stored_refresh_token() {
+            let mut client = new_client();
+
+            match client.refresh().await {
+                Err(Error::MissingRefreshToken) => (),
+                other => panic!("Expected Error::MissingRefreshToken but got {other:?}"),
+            }
+        }
+
+        #[tokio::test]
+        async fn redeems_the_stored_refresh_token() {
+            let _m = auth_mock();
+            let mut client = new_client();
+            client.refresh_token = Some("refresh_token".to_owned());
+
+            client.refresh().await.unwrap();
+            assert_eq!(client.access_token, Some("access_token".to_owned()));
+        }
+    }
+
+    mod sign_client_assertion {
+        use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation as JwtValidation};
+
+        use super::*;
+
+        fn private_key_client() -> Auth0Client {
+            let private_key = std::fs::read("tests/data/private_key.pem").unwrap();
+
+            Auth0Client::new_with_private_key_jwt(
+                "client_id",
+                &mockito::server_url(),
+                "https://audience.com",
+                private_key,
+            )
+        }
+
+        #[test]
+        fn signs_an_rs256_assertion_with_the_expected_claims() {
+            let client = private_key_client();
+            let assertion = client
+                .sign_client_assertion(client.private_key.as_ref().unwrap())
+                .unwrap();
+
+            let public_key = std::fs::read("tests/data/public_key.pem").unwrap();
+            let mut validation = JwtValidation::new(Algorithm::RS256);
+            validation.set_audience(&[format!("{}/oauth/token", mockito::server_url())
+                .replace("//", "/")]);
+            validation.required_spec_claims.clear();
+
+            let claims = decode::<serde_json::Value>(
+                &assertion,
+                &DecodingKey::from_rsa_pem(&public_key).unwrap(),
+                &validation,
+            )
+            .unwrap()
+            .claims;
+
+            assert_eq!(claims["iss"], "client_id");
+            assert_eq!(claims["sub"], "client_id");
+            assert!(claims["jti"].is_string());
+        }
+
+        #[tokio::test]
+        async fn authenticates_with_a_client_assertion_instead_of_a_secret() {
+            let _m = auth_mock();
+            let mut client = private_key_client();
+
+            client.authenticate().await.unwrap();
+            assert_eq!(client.access_token, Some("access_token".to_owned()));
+        }
+    }
+
+    mod access_token_valid {
+        use super::*;
+
+        #[test]
+        fn return_false_when_not_authenticated() {
+            let client = new_client();
+
+            assert!(!client.access_token_valid());
+        }
+
+        #[tokio::test]
+        async fn return_true_when_freshly_authenticated() {
+            let _m = auth_mock();
+            let mut client = new_client();
+
+            client.authenticate().await.unwrap();
+            assert!(client.access_token_valid());
+        }
+
+        #[tokio::test]
+        async fn return_false_when_expired() {
+            let _m = auth_mock();
+            let mut client = new_client();
+
+            client.authenticate().await.unwrap();
+            client.token_expires_at = Some(Utc::now() - Duration::seconds(1));
+            assert!(!client.access_token_valid());
+        }
+    }
+
+    mod valid_access_token {
+        use super::*;
+
+        #[tokio::test]
+        async fn authenticates_when_no_token_is_cached() {
+            let _m = auth_mock();
+            let mut client = new_client();
+
+            let token = client.valid_access_token().await.unwrap();
+            assert_eq!(token, "access_token");
+        }
+
+        #[tokio::test]
+        async fn reuses_the_cached_token_when_still_valid() {
+            let _m = auth_mock();
+            let mut client = new_client();
+
+            let first = client.valid_access_token().await.unwrap();
+            let expiry = client.token_expires_at;
+            let second = client.valid_access_token().await.unwrap();
+
+            assert_eq!(first, second);
+            assert_eq!(expiry, client.token_expires_at);
+        }
+
+        #[tokio::test]
+        async fn redeems_the_refresh_token_instead_of_reauthenticating_when_expired() {
+            let _m = mock("POST", "/oauth/token")
+                .match_body(mockito::Matcher::PartialJson(
+                    json!({ "grant_type": "refresh_token" }),
+                ))
+                .with_status(200)
+                .with_body(
+                    json!({
+                        "access_token": "refreshed_access_token",
+                        "token_type": "Bearer",
+                        "expires_in": 86400,
+                    })
+                    .to_string(),
+                )
+                .create();
+            let mut client = new_client();
+            client.refresh_token = Some("refresh_token".to_owned());
+            client.token_expires_at = Some(Utc::now() - Duration::seconds(1));
+
+            let token = client.valid_access_token().await.unwrap();
+            assert_eq!(token, "refreshed_access_token");
+        }
     }
 
     mod access_token {
@@ -263,5 +882,164 @@ mod tests {
                 }
             }
         }
+
+        mod valid_jwt_cached {
+            use std::time::Duration;
+
+            use super::*;
+
+            #[tokio::test]
+            async fn validate_valid_jwt() {
+                let _m = jwks_mock();
+                let valid_token = std::fs::read_to_string("tests/data/valid_jwt.txt").unwrap();
+                let cache = JwksCache::new(Duration::from_secs(300));
+
+                valid_jwt_cached(
+                    &cache,
+                    &valid_token,
+                    &mockito::server_url(),
+                    vec![Validation::SubjectPresent],
+                )
+                .await
+                .unwrap();
+            }
+
+            #[tokio::test]
+            async fn reuses_the_cached_jwks_without_refetching() {
+                let valid_token = std::fs::read_to_string("tests/data/valid_jwt.txt").unwrap();
+                let cache = JwksCache::new(Duration::from_secs(300));
+
+                {
+                    let _m = jwks_mock();
+                    valid_jwt_cached(
+                        &cache,
+                        &valid_token,
+                        &mockito::server_url(),
+                        vec![Validation::SubjectPresent],
+                    )
+                    .await
+                    .unwrap();
+                }
+
+                // The mock is gone now: a second call only succeeds if the
+                // JWKS came from the cache instead of a fresh HTTP fetch.
+                valid_jwt_cached(
+                    &cache,
+                    &valid_token,
+                    &mockito::server_url(),
+                    vec![Validation::SubjectPresent],
+                )
+                .await
+                .unwrap();
+            }
+
+            #[tokio::test]
+            async fn refreshes_once_on_an_unknown_kid() {
+                let jwks_response = std::fs::read_to_string("tests/data/jwks_no_key.json").unwrap();
+                let m = mock("GET", "/.well-known/jwks.json")
+                    .with_status(200)
+                    .with_body(jwks_response)
+                    .expect(2)
+                    .create();
+                let valid_token = std::fs::read_to_string("tests/data/valid_jwt.txt").unwrap();
+                let cache = JwksCache::new(Duration::from_secs(300));
+                let res = valid_jwt_cached(
+                    &cache,
+                    &valid_token,
+                    &mockito::server_url(),
+                    vec![Validation::SubjectPresent],
+                )
+                .await;
+
+                match res {
+                    Err(Error::JwtMissingKid) => (),
+                    Err(err) => panic!("Expected Error::JwtMissingKid but got {err:?}"),
+                    _ => panic!("Expected Error::JwtMissingKid but got a valid JWT"),
+                }
+                // One fetch on the cache miss, one forced refresh for the unknown kid.
+                m.assert();
+            }
+        }
+
+        mod valid_jwt_claims {
+            use serde::Deserialize;
+
+            use super::*;
+
+            #[derive(Deserialize)]
+            struct Claims {
+                sub: String,
+            }
+
+            #[tokio::test]
+            async fn deserializes_the_validated_claims() {
+                let _m = jwks_mock();
+                let valid_token = std::fs::read_to_string("tests/data/valid_jwt.txt").unwrap();
+
+                let claims: Claims = valid_jwt_claims(
+                    &valid_token,
+                    &mockito::server_url(),
+                    vec![Validation::SubjectPresent],
+                )
+                .await
+                .unwrap();
+
+                assert!(!claims.sub.is_empty());
+            }
+        }
+
+        mod valid_jwt_claims_cached {
+            use std::time::Duration;
+
+            use serde::Deserialize;
+
+            use super::*;
+
+            #[derive(Deserialize)]
+            struct Claims {
+                sub: String,
+            }
+
+            #[tokio::test]
+            async fn deserializes_the_validated_claims() {
+                let _m = jwks_mock();
+                let valid_token = std::fs::read_to_string("tests/data/valid_jwt.txt").unwrap();
+                let cache = JwksCache::new(Duration::from_secs(300));
+
+                let claims: Claims = valid_jwt_claims_cached(
+                    &cache,
+                    &valid_token,
+                    &mockito::server_url(),
+                    vec![Validation::SubjectPresent],
+                )
+                .await
+                .unwrap();
+
+                assert!(!claims.sub.is_empty());
+            }
+        }
+
+        mod scopes_from_claims {
+            use serde_json::json;
+
+            use super::*;
+
+            #[test]
+            fn splits_the_scope_claim_on_whitespace() {
+                let claims = json!({ "scope": "read:users write:users" });
+
+                assert_eq!(
+                    scopes_from_claims(&claims),
+                    vec!["read:users".to_owned(), "write:users".to_owned()]
+                );
+            }
+
+            #[test]
+            fn returns_an_empty_vec_when_absent() {
+                let claims = json!({ "sub": "user" });
+
+                assert!(scopes_from_claims(&claims).is_empty());
+            }
+        }
     }
 }
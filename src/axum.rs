@@ -0,0 +1,223 @@
+//! An optional [`axum`] integration: an extractor that validates the bearer
+//! token from the `Authorization` header and exposes its claims, so services
+//! don't have to hand-write that middleware themselves.
+//!
+//! Requires the `axum` feature.
+
+use std::sync::Arc;
+
+use ::axum::extract::{FromRef, FromRequestParts};
+use ::axum::http::request::Parts;
+use ::axum::http::StatusCode;
+use ::axum::response::{IntoResponse, Response};
+use ::axum::RequestPartsExt;
+use ::axum_extra::headers::authorization::Bearer;
+use ::axum_extra::headers::Authorization;
+use ::axum_extra::TypedHeader;
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+
+use crate::authorization::{scopes_from_claims, valid_jwt_claims_cached};
+use crate::error::Error;
+use crate::jwks::JwksCache;
+
+/// Configuration for validating bearer tokens in an Axum handler.
+///
+/// Make it available to [`Auth0User`] by implementing [`FromRef`] for your
+/// application state (or using it directly as the state). The `cache` is
+/// shared across requests so the JWKS isn't refetched on every call, the way
+/// [`crate::authorization::valid_jwt_claims_cached`] expects.
+#[derive(Clone)]
+pub struct Auth0Config {
+    /// The authority to retrieve the JWKS from.
+    pub authority: String,
+    /// Builds the validations to run on each incoming token.
+    ///
+    /// A factory rather than a `Vec` because [`alcoholic_jwt::Validation`]
+    /// isn't `Clone`, and the validation list is consumed by value on every
+    /// request.
+    pub validations: Arc<dyn Fn() -> Vec<alcoholic_jwt::Validation> + Send + Sync>,
+    /// Scopes that must all be present in the token's `scope` claim.
+    pub required_scopes: Vec<String>,
+    /// The JWKS cache shared across requests handled by this config.
+    pub cache: Arc<JwksCache>,
+}
+
+/// Extracts and validates the bearer token from the `Authorization` header,
+/// deserializing its claims into `T`.
+///
+/// # Example
+///
+/// ```ignore
+/// async fn handler(Auth0User(claims): Auth0User<MyClaims>) { /* ... */ }
+/// ```
+pub struct Auth0User<T>(pub T);
+
+/// Why an [`Auth0User`] extraction failed.
+pub enum Auth0Rejection {
+    /// No (or a malformed) `Authorization: Bearer` header was present.
+    MissingToken,
+    /// The token failed validation or claims deserialization.
+    Invalid(Error),
+    /// The token is valid but missing one or more required scopes.
+    MissingScopes,
+}
+
+impl IntoResponse for Auth0Rejection {
+    fn into_response(self) -> Response {
+        match self {
+            Auth0Rejection::MissingToken => {
+                (StatusCode::UNAUTHORIZED, "missing bearer token").into_response()
+            }
+            Auth0Rejection::Invalid(err) => {
+                (StatusCode::UNAUTHORIZED, err.to_string()).into_response()
+            }
+            Auth0Rejection::MissingScopes => {
+                (StatusCode::FORBIDDEN, "missing required scope").into_response()
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<S, T> FromRequestParts<S> for Auth0User<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+    Auth0Config: FromRef<S>,
+{
+    type Rejection = Auth0Rejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let config = Auth0Config::from_ref(state);
+
+        let TypedHeader(Authorization(bearer)) = parts
+            .extract::<TypedHeader<Authorization<Bearer>>>()
+            .await
+            .map_err(|_| Auth0Rejection::MissingToken)?;
+
+        let claims: serde_json::Value = valid_jwt_claims_cached(
+            &config.cache,
+            bearer.token(),
+            &config.authority,
+            (config.validations)(),
+        )
+        .await
+        .map_err(Auth0Rejection::Invalid)?;
+
+        let granted_scopes = scopes_from_claims(&claims);
+        if !config
+            .required_scopes
+            .iter()
+            .all(|scope| granted_scopes.contains(scope))
+        {
+            return Err(Auth0Rejection::MissingScopes);
+        }
+
+        let claims = serde_json::from_value(claims)
+            .map_err(Error::ClaimsDeserialization)
+            .map_err(Auth0Rejection::Invalid)?;
+
+        Ok(Auth0User(claims))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use ::axum::body::Body;
+    use ::axum::http::Request;
+    use ::axum::routing::get;
+    use ::axum::Router;
+    use serde::Deserialize;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Claims {
+        sub: String,
+    }
+
+    fn config(required_scopes: Vec<String>) -> Auth0Config {
+        Auth0Config {
+            authority: mockito::server_url(),
+            validations: Arc::new(|| vec![alcoholic_jwt::Validation::SubjectPresent]),
+            required_scopes,
+            cache: Arc::new(JwksCache::new(Duration::from_secs(300))),
+        }
+    }
+
+    fn app(config: Auth0Config) -> Router {
+        Router::new()
+            .route(
+                "/",
+                get(|Auth0User(claims): Auth0User<Claims>| async move { claims.sub }),
+            )
+            .with_state(config)
+    }
+
+    fn jwks_mock() -> mockito::Mock {
+        let jwks_response = std::fs::read_to_string("tests/data/jwks.json").unwrap();
+
+        mockito::mock("GET", "/.well-known/jwks.json")
+            .with_status(200)
+            .with_body(jwks_response)
+            .create()
+    }
+
+    fn request(bearer: Option<&str>) -> Request<Body> {
+        let mut builder = Request::builder().uri("/");
+        if let Some(bearer) = bearer {
+            builder = builder.header("Authorization", format!("Bearer {bearer}"));
+        }
+
+        builder.body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn missing_bearer_header_is_unauthorized() {
+        let response = app(config(vec![])).oneshot(request(None)).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn invalid_token_is_unauthorized() {
+        let _m = jwks_mock();
+
+        let response = app(config(vec![]))
+            .oneshot(request(Some("not-a-jwt")))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn insufficient_scopes_is_forbidden() {
+        let _m = jwks_mock();
+        let valid_token = std::fs::read_to_string("tests/data/valid_jwt.txt").unwrap();
+
+        let response = app(config(vec!["admin".to_owned()]))
+            .oneshot(request(Some(&valid_token)))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn valid_token_extracts_claims() {
+        let _m = jwks_mock();
+        let valid_token = std::fs::read_to_string("tests/data/valid_jwt.txt").unwrap();
+
+        let response = app(config(vec![]))
+            .oneshot(request(Some(&valid_token)))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,50 @@
+//! Error types returned by this crate.
+
+use thiserror::Error as ThisError;
+
+/// A specialized `Result` type for this crate's fallible operations.
+pub type Auth0Result<T> = Result<T, Error>;
+
+/// The error type returned by this crate.
+#[derive(Debug, ThisError)]
+pub enum Error {
+    /// The HTTP request to Auth0 failed.
+    #[error("http request failed: {0}")]
+    Http(#[from] reqwest::Error),
+
+    /// The response body could not be parsed as JSON.
+    #[error("failed to parse response: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// The JWT could not be decoded, carried no `kid`, or its `kid` matches
+    /// no key in the JWKS.
+    #[error("the token's `kid` is missing from the JWKS")]
+    JwtMissingKid,
+
+    /// The JWT failed validation.
+    #[error("the token is invalid: {0}")]
+    InvalidJwt(#[from] alcoholic_jwt::ValidationError),
+
+    /// The validated JWT's claims could not be deserialized into the
+    /// requested type.
+    #[error("failed to deserialize claims: {0}")]
+    ClaimsDeserialization(serde_json::Error),
+
+    /// The Auth0 token endpoint rejected the request, e.g. an invalid
+    /// client, an unauthorized grant, or rate limiting.
+    #[error("token endpoint returned `{error}` (status {status}): {error_description:?}")]
+    TokenEndpoint {
+        error: String,
+        error_description: Option<String>,
+        status: reqwest::StatusCode,
+    },
+
+    /// [`crate::Auth0Client::refresh`] was called but the client has no
+    /// refresh token to redeem.
+    #[error("no refresh token is available on this client")]
+    MissingRefreshToken,
+
+    /// Building or signing the `private_key_jwt` client assertion failed.
+    #[error("failed to build client assertion: {0}")]
+    ClientAssertionSigning(jsonwebtoken::errors::Error),
+}
@@ -0,0 +1,68 @@
+//! A small TTL cache for JWKS documents, so validating many tokens against
+//! the same authority doesn't refetch signing keys on every call.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use alcoholic_jwt::JWKS;
+use tokio::sync::RwLock;
+use tokio::time::Instant;
+
+use crate::authorization::fetch_jwks;
+use crate::error::Auth0Result;
+
+/// A cached JWKS document, along with the instant it was fetched.
+struct CachedJwks {
+    jwks: JWKS,
+    fetched_at: Instant,
+}
+
+/// A TTL-based cache of JWKS documents, keyed by authority URI.
+///
+/// Share one `JwksCache` across calls to [`valid_jwt_cached`](crate::authorization::valid_jwt_cached)
+/// to avoid doing a full HTTP round trip and JSON parse on every token
+/// validation.
+pub struct JwksCache {
+    ttl: Duration,
+    entries: RwLock<HashMap<String, CachedJwks>>,
+}
+
+impl JwksCache {
+    /// Creates a new cache that reuses a given authority's fetched keys for
+    /// up to `ttl` before refetching them. A forced refresh, done when a
+    /// token's `kid` isn't in the cached set, refetches regardless of the TTL.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the cached JWKS for `authority`, fetching and storing it on a
+    /// cache miss or once the TTL has elapsed.
+    pub(crate) async fn get(&self, authority: &str) -> Auth0Result<JWKS> {
+        if let Some(entry) = self.entries.read().await.get(authority) {
+            if entry.fetched_at.elapsed() < self.ttl {
+                return Ok(entry.jwks.clone());
+            }
+        }
+
+        self.refresh(authority).await
+    }
+
+    /// Forces a refetch of `authority`'s keys, bypassing the TTL. Used when a
+    /// `kid` can't be found in the cached set, to tolerate key rotation.
+    pub(crate) async fn refresh(&self, authority: &str) -> Auth0Result<JWKS> {
+        let jwks = fetch_jwks(&format!("{authority}/.well-known/jwks.json")).await?;
+
+        self.entries.write().await.insert(
+            authority.to_owned(),
+            CachedJwks {
+                jwks: jwks.clone(),
+                fetched_at: Instant::now(),
+            },
+        );
+
+        Ok(jwks)
+    }
+}
@@ -0,0 +1,95 @@
+//! A lightweight async client for authenticating against and validating
+//! tokens issued by an Auth0 tenant.
+
+pub mod authorization;
+#[cfg(feature = "axum")]
+pub mod axum;
+pub mod error;
+pub mod jwks;
+
+/// The OAuth2 grant type used when authenticating against Auth0.
+#[derive(Debug, Clone, Copy)]
+pub enum GrantType {
+    ClientCredentials,
+    AuthorizationCode,
+    RefreshToken,
+}
+
+impl std::fmt::Display for GrantType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GrantType::ClientCredentials => write!(f, "client_credentials"),
+            GrantType::AuthorizationCode => write!(f, "authorization_code"),
+            GrantType::RefreshToken => write!(f, "refresh_token"),
+        }
+    }
+}
+
+/// An Auth0 client, holding the configuration and state needed to
+/// authenticate and validate tokens against an Auth0 tenant.
+pub struct Auth0Client {
+    pub(crate) domain: String,
+    pub(crate) client_id: String,
+    pub(crate) client_secret: String,
+    pub(crate) audience: String,
+    pub(crate) grant_type: GrantType,
+    pub(crate) http_client: reqwest::Client,
+    pub(crate) access_token: Option<String>,
+    pub(crate) token_expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub(crate) refresh_token: Option<String>,
+    pub(crate) private_key: Option<Vec<u8>>,
+}
+
+impl Auth0Client {
+    /// Creates a new `Auth0Client` configured for the client-credentials grant.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let client =
+    ///     auth0_client::Auth0Client::new("client_id", "client_secret", "domain", "audience");
+    /// ```
+    pub fn new(client_id: &str, client_secret: &str, domain: &str, audience: &str) -> Self {
+        Self {
+            domain: domain.to_owned(),
+            client_id: client_id.to_owned(),
+            client_secret: client_secret.to_owned(),
+            audience: audience.to_owned(),
+            grant_type: GrantType::ClientCredentials,
+            http_client: reqwest::Client::new(),
+            access_token: None,
+            token_expires_at: None,
+            refresh_token: None,
+            private_key: None,
+        }
+    }
+
+    /// Creates a new `Auth0Client` that authenticates with a signed
+    /// `private_key_jwt` client assertion instead of a plaintext client
+    /// secret, as Auth0 recommends for high-security deployments.
+    ///
+    /// `private_key` is an RSA private key, either PEM- or DER-encoded, and
+    /// may come from an env var or a secret manager rather than a file path.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// let client = auth0_client::Auth0Client::new_with_private_key_jwt(
+    ///     "client_id",
+    ///     "domain",
+    ///     "audience",
+    ///     std::fs::read("key.pem").unwrap(),
+    /// );
+    /// ```
+    pub fn new_with_private_key_jwt(
+        client_id: &str,
+        domain: &str,
+        audience: &str,
+        private_key: Vec<u8>,
+    ) -> Self {
+        Self {
+            private_key: Some(private_key),
+            ..Self::new(client_id, "", domain, audience)
+        }
+    }
+}